@@ -1,19 +1,13 @@
-use poker_ranking::PokerHand;
+use poker_ranking::{JokerWild, PokerError, PokerHand, RankingStrategy, Standard};
 
-/// Given a list of poker hands, return a list of those hands which win.
-///
-/// Note the type signature: this function should return _the same_ reference to
-/// the winning hand(s) as were passed in, not reconstructed strings which happen to be equal.
-pub fn winning_hands<'a>(hands: &[&'a str]) -> Vec<&'a str> {
-    let (_, mut poker_hands) = hands.iter().fold(
-        (0, Vec::with_capacity(hands.len())),
-        |(idx, mut phands), &handin| {
-            phands.insert(idx, PokerHand::from_parse(idx, handin));
-            (idx + 1, phands)
-        },
-    );
-
-    poker_hands.sort_by(|a, b| b.cmp(&a));
+/// Sorts `poker_hands` best-first and collects the indices tied for the win
+/// back into the original `hands` strings. Shared by all `winning_hands*`
+/// entry points so the sort/tie-break logic only lives in one place.
+fn collect_winners<'a, S: RankingStrategy>(
+    hands: &[&'a str],
+    mut poker_hands: Vec<PokerHand<S>>,
+) -> Vec<&'a str> {
+    poker_hands.sort_by(|a, b| b.cmp(a));
 
     let mut ret = Vec::with_capacity(poker_hands.len());
     for (idx, ph) in poker_hands.iter().enumerate() {
@@ -23,14 +17,55 @@ pub fn winning_hands<'a>(hands: &[&'a str]) -> Vec<&'a str> {
             break;
         }
     }
-    return ret;
+    ret
+}
+
+/// Given a list of poker hands, return a list of those hands which win.
+///
+/// Note the type signature: this function should return _the same_ reference to
+/// the winning hand(s) as were passed in, not reconstructed strings which happen to be equal.
+pub fn winning_hands<'a>(hands: &[&'a str]) -> Vec<&'a str> {
+    let poker_hands = hands
+        .iter()
+        .enumerate()
+        .map(|(idx, &handin)| PokerHand::<Standard>::from_parse(idx, handin))
+        .collect();
+
+    collect_winners(hands, poker_hands)
+}
+
+/// Like [`winning_hands`], but ranks hands under the [`JokerWild`] house
+/// rule: any card whose suit is a wildcard acts as a joker that joins the
+/// hand's largest group of equal values, which can promote the hand up to
+/// `PokerRank::FiveOfAKind`. A joker does not stand in for a missing card of
+/// a straight or a flush; there only its own parsed value counts.
+pub fn winning_hands_wild<'a>(hands: &[&'a str]) -> Vec<&'a str> {
+    let poker_hands = hands
+        .iter()
+        .enumerate()
+        .map(|(idx, &handin)| PokerHand::<JokerWild>::from_parse(idx, handin))
+        .collect();
+
+    collect_winners(hands, poker_hands)
+}
+
+/// Like [`winning_hands`], but reports the first parse failure instead of
+/// panicking, so untrusted hand strings can be validated before use.
+pub fn try_winning_hands<'a>(hands: &[&'a str]) -> Result<Vec<&'a str>, PokerError> {
+    let mut poker_hands = Vec::with_capacity(hands.len());
+    for (idx, &handin) in hands.iter().enumerate() {
+        poker_hands.push(PokerHand::<Standard>::try_from_parse(idx, handin)?);
+    }
+
+    Ok(collect_winners(hands, poker_hands))
 }
 
 mod poker_ranking {
-    use std::{collections::HashMap, ops::Deref};
+    use std::collections::HashMap;
 
     #[derive(PartialEq, Eq, Copy, Clone)]
     pub enum PokerRank {
+        FiveOfAKind = 10,
         StraightFlush = 9,
         FourOfAKind = 8,
         FullHouse = 7,
@@ -64,13 +99,24 @@ mod poker_ranking {
     }
 
     impl CardSuit {
+        /// Parses a suit letter, falling back to [`CardSuit::Joker`] for
+        /// anything unrecognized rather than erroring. Kept for
+        /// [`Card::parse`], which must preserve `from_parse`'s long-standing
+        /// leniency; [`Card::try_from`] uses [`try_from_abbrev`](Self::try_from_abbrev) instead.
         pub fn from_abbrev(ch: char) -> Self {
+            Self::try_from_abbrev(ch).unwrap_or(Self::Joker)
+        }
+
+        /// Parses a suit letter (`S`/`C`/`D`/`H`, case-insensitive) or the `*`
+        /// wildcard token into a [`CardSuit`], rejecting anything else.
+        pub fn try_from_abbrev(ch: char) -> Result<Self, PokerError> {
             match ch.to_ascii_uppercase() {
-                'S' => Self::Spade,
-                'C' => Self::Club,
-                'D' => Self::Diamond,
-                'H' => Self::Heart,
-                _ => Self::Joker,
+                'S' => Ok(Self::Spade),
+                'C' => Ok(Self::Club),
+                'D' => Ok(Self::Diamond),
+                'H' => Ok(Self::Heart),
+                '*' => Ok(Self::Joker),
+                _ => Err(PokerError::InvalidSuit(ch)),
             }
         }
     }
@@ -87,24 +133,169 @@ mod poker_ranking {
         }
     }
 
+    /// Why a hand string couldn't be parsed by [`PokerHand::try_from_parse`].
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub enum PokerError {
+        InvalidRank(String),
+        InvalidSuit(char),
+        WrongCardCount(usize),
+        EmptyHand,
+    }
+
+    impl std::fmt::Display for PokerError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::InvalidRank(rank) => write!(f, "invalid card rank: {rank:?}"),
+                Self::InvalidSuit(suit) => write!(f, "invalid card suit: {suit:?}"),
+                Self::WrongCardCount(count) => write!(f, "expected 5 cards, found {count}"),
+                Self::EmptyHand => write!(f, "hand is empty"),
+            }
+        }
+    }
+
+    impl std::error::Error for PokerError {}
+
     #[derive(Clone)]
     pub struct Card {
         suit: CardSuit,
         value: u16,
     }
 
-    pub struct PokerHand {
+    impl Card {
+        /// Parses a single card token, e.g. `"AS"`, `"10H"`, or the equivalent
+        /// single-character `"TH"`, panicking on an unrecognized rank. Unlike
+        /// [`TryFrom<&str>`](Card#impl-TryFrom<&str>-for-Card), an unrecognized
+        /// suit character is treated as a joker rather than erroring, matching
+        /// this method's long-standing behavior; use `try_from` for a version
+        /// that's strict on both rank and suit.
+        pub fn parse(card: &str) -> Self {
+            if card.is_empty() {
+                panic!("{}", PokerError::InvalidRank(card.to_string()));
+            }
+
+            let suit = CardSuit::from_abbrev(card.chars().last().unwrap());
+            let rank = card.chars().take(card.len() - 1).collect::<String>();
+            let value = Self::parse_rank(&rank).unwrap();
+
+            Card { suit, value }
+        }
+
+        /// Shared rank-parsing logic for [`Card::parse`] and `TryFrom<&str>`.
+        fn parse_rank(rank: &str) -> Result<u16, PokerError> {
+            match rank.parse::<u16>() {
+                Ok(i) => Ok(i),
+                Err(_) => match rank {
+                    "A" => Ok(14_u16),
+                    "K" => Ok(13_u16),
+                    "Q" => Ok(12_u16),
+                    "J" => Ok(11_u16),
+                    "T" | "t" => Ok(10_u16),
+                    _ => Err(PokerError::InvalidRank(rank.to_string())),
+                },
+            }
+        }
+    }
+
+    impl TryFrom<&str> for Card {
+        type Error = PokerError;
+
+        fn try_from(card: &str) -> Result<Self, PokerError> {
+            if card.is_empty() {
+                return Err(PokerError::InvalidRank(card.to_string()));
+            }
+
+            let suit = CardSuit::try_from_abbrev(card.chars().last().unwrap())?;
+            let rank = card.chars().take(card.len() - 1).collect::<String>();
+            let value = Self::parse_rank(&rank)?;
+
+            Ok(Card { suit, value })
+        }
+    }
+
+    /// Hooks a ranking variant uses to steer `rank_hand` without forking its
+    /// match arms: how two cards compare to each other, and how the raw
+    /// value -> cards groupings are massaged before a category is read off
+    /// them. The default methods reproduce plain five-card-draw behavior, so
+    /// a new house rule only needs to override what it actually changes.
+    pub trait RankingStrategy {
+        /// Per-card ordering key used for kicker comparisons; higher wins.
+        fn card_strength(card: &Card) -> u32 {
+            card.value as u32
+        }
+
+        /// Adjusts the value -> cards groups built from a hand's five cards,
+        /// in place, before group sizes are read off to settle on a
+        /// [`PokerRank`]. The default leaves the groups untouched.
+        fn adjust_counts(_counts: &mut HashMap<u16, Vec<Card>>) {}
+    }
+
+    /// Conventional five-card-draw rules: no wildcards.
+    pub struct Standard;
+
+    impl RankingStrategy for Standard {}
+
+    /// House rule where any card using the `*` wildcard suit token (parsed as
+    /// `CardSuit::Joker`) is a joker: it always loses a kicker comparison to a
+    /// real card, and is folded into the hand's largest existing group before
+    /// the category is decided, which can promote a hand all the way to
+    /// [`PokerRank::FiveOfAKind`]. Note that [`PokerHand::from_parse`]'s
+    /// leniency on unrecognized suit characters means any garbled suit, not
+    /// just `*`, becomes a joker under this strategy.
+    pub struct JokerWild;
+
+    impl RankingStrategy for JokerWild {
+        fn card_strength(card: &Card) -> u32 {
+            if card.suit == CardSuit::Joker {
+                0
+            } else {
+                card.value as u32
+            }
+        }
+
+        fn adjust_counts(counts: &mut HashMap<u16, Vec<Card>>) {
+            let jokers: Vec<Card> = counts
+                .values_mut()
+                .flat_map(|group| {
+                    let (jokers, rest): (Vec<Card>, Vec<Card>) =
+                        group.drain(..).partition(|c| c.suit == CardSuit::Joker);
+                    *group = rest;
+                    jokers
+                })
+                .collect();
+            counts.retain(|_, group| !group.is_empty());
+
+            if jokers.is_empty() {
+                return;
+            }
+
+            if counts.is_empty() {
+                // A hand of all jokers maximizes as five of the highest value.
+                counts.insert(14, jokers);
+                return;
+            }
+
+            let best_value = *counts
+                .iter()
+                .max_by_key(|&(value, group)| (group.len(), *value))
+                .unwrap()
+                .0;
+            counts.get_mut(&best_value).unwrap().extend(jokers);
+        }
+    }
+
+    pub struct PokerHand<S: RankingStrategy = Standard> {
         id: usize,
         cards: Vec<Card>,
         rank: PokerRank,
         rank_swapped_aces: bool,
         cards_ranked: Vec<Card>,
         spares: Vec<Card>,
+        strategy: std::marker::PhantomData<S>,
     }
 
-    impl Eq for PokerHand {}
+    impl<S: RankingStrategy> Eq for PokerHand<S> {}
 
-    impl PartialEq for PokerHand {
+    impl<S: RankingStrategy> PartialEq for PokerHand<S> {
         fn eq(&self, other: &Self) -> bool {
             self.rank == other.rank
                 && self
@@ -115,13 +306,13 @@ mod poker_ranking {
         }
     }
 
-    impl Ord for PokerHand {
+    impl<S: RankingStrategy> Ord for PokerHand<S> {
         fn cmp(&self, other: &Self) -> std::cmp::Ordering {
             self.partial_cmp(other).unwrap()
         }
     }
 
-    impl PartialOrd for PokerHand {
+    impl<S: RankingStrategy> PartialOrd for PokerHand<S> {
         fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
             if self.eq(other) {
                 Some(std::cmp::Ordering::Equal)
@@ -130,24 +321,28 @@ mod poker_ranking {
                     self.rank.partial_cmp(&other.rank)
                 } else {
                     match self.rank {
+                        PokerRank::FiveOfAKind => {
+                            return S::card_strength(&self.cards_ranked[0])
+                                .partial_cmp(&S::card_strength(&other.cards_ranked[0]));
+                        }
                         PokerRank::StraightFlush => {
-                            return self.cards_ranked[0]
-                                .value
-                                .partial_cmp(&other.cards_ranked[0].value);
+                            return S::card_strength(&self.cards_ranked[0])
+                                .partial_cmp(&S::card_strength(&other.cards_ranked[0]));
                         }
                         PokerRank::FourOfAKind => {
-                            if self.cards_ranked[0].value == other.cards_ranked[0].value {
-                                return self.spares[0].value.partial_cmp(&other.spares[0].value);
+                            if S::card_strength(&self.cards_ranked[0])
+                                == S::card_strength(&other.cards_ranked[0])
+                            {
+                                return S::card_strength(&self.spares[0])
+                                    .partial_cmp(&S::card_strength(&other.spares[0]));
                             } else {
-                                return self.cards_ranked[0]
-                                    .value
-                                    .partial_cmp(&other.cards_ranked[0].value);
+                                return S::card_strength(&self.cards_ranked[0])
+                                    .partial_cmp(&S::card_strength(&other.cards_ranked[0]));
                             }
                         }
                         PokerRank::FullHouse => {
-                            let ord = self.cards_ranked[0]
-                                .value
-                                .partial_cmp(&other.cards_ranked[0].value)
+                            let ord = S::card_strength(&self.cards_ranked[0])
+                                .partial_cmp(&S::card_strength(&other.cards_ranked[0]))
                                 .unwrap();
 
                             match ord {
@@ -155,17 +350,15 @@ mod poker_ranking {
                                     return Some(ord);
                                 }
                                 std::cmp::Ordering::Equal => {
-                                    return self.cards_ranked[3]
-                                        .value
-                                        .partial_cmp(&other.cards_ranked[3].value);
+                                    return S::card_strength(&self.cards_ranked[3])
+                                        .partial_cmp(&S::card_strength(&other.cards_ranked[3]));
                                 }
                             }
                         }
                         PokerRank::Flush => {
                             for (idx, card) in self.cards_ranked.iter().enumerate() {
-                                let ord = card
-                                    .value
-                                    .partial_cmp(&other.cards_ranked[idx].value)
+                                let ord = S::card_strength(card)
+                                    .partial_cmp(&S::card_strength(&other.cards_ranked[idx]))
                                     .unwrap();
                                 match ord {
                                     std::cmp::Ordering::Less | std::cmp::Ordering::Greater => {
@@ -179,7 +372,9 @@ mod poker_ranking {
 
                         PokerRank::HighCard => {
                             for (idx, card) in self.spares.iter().enumerate() {
-                                let ord = card.value.partial_cmp(&other.spares[idx].value).unwrap();
+                                let ord = S::card_strength(card)
+                                    .partial_cmp(&S::card_strength(&other.spares[idx]))
+                                    .unwrap();
                                 match ord {
                                     std::cmp::Ordering::Less | std::cmp::Ordering::Greater => {
                                         return Some(ord);
@@ -191,19 +386,16 @@ mod poker_ranking {
                         }
 
                         PokerRank::Straight => {
-                            return self.cards_ranked[0]
-                                .value
-                                .partial_cmp(&other.cards_ranked[0].value);
+                            return S::card_strength(&self.cards_ranked[0])
+                                .partial_cmp(&S::card_strength(&other.cards_ranked[0]));
                         }
                         PokerRank::ThreeOfAKind | PokerRank::TwoPair | PokerRank::OnePair => {
-                            let mut ord = self.cards_ranked[0]
-                                .value
-                                .partial_cmp(&other.cards_ranked[0].value)
+                            let mut ord = S::card_strength(&self.cards_ranked[0])
+                                .partial_cmp(&S::card_strength(&other.cards_ranked[0]))
                                 .unwrap();
                             if ord == std::cmp::Ordering::Equal && self.rank == PokerRank::TwoPair {
-                                ord = self.cards_ranked[2]
-                                    .value
-                                    .partial_cmp(&other.cards_ranked[2].value)
+                                ord = S::card_strength(&self.cards_ranked[2])
+                                    .partial_cmp(&S::card_strength(&other.cards_ranked[2]))
                                     .unwrap();
                             }
 
@@ -213,9 +405,8 @@ mod poker_ranking {
                                 }
                                 std::cmp::Ordering::Equal => {
                                     for (idx, card) in self.spares.iter().enumerate() {
-                                        let ord = card
-                                            .value
-                                            .partial_cmp(&other.spares[idx].value)
+                                        let ord = S::card_strength(card)
+                                            .partial_cmp(&S::card_strength(&other.spares[idx]))
                                             .unwrap();
                                         match ord {
                                             std::cmp::Ordering::Less
@@ -239,7 +430,7 @@ mod poker_ranking {
         }
     }
 
-    impl PokerHand {
+    impl<S: RankingStrategy> PokerHand<S> {
         pub fn index(&self) -> usize {
             self.id
         }
@@ -251,38 +442,8 @@ mod poker_ranking {
                 rank_swapped_aces: false,
                 spares: Vec::new(),
                 cards_ranked: Vec::new(),
-                cards: phand
-                    .split(" ")
-                    .fold(Vec::with_capacity(5), |mut cards, card| {
-                        cards.push(Card {
-                            suit: CardSuit::from_abbrev(card.chars().last().unwrap()),
-                            value: match card
-                                .chars()
-                                .take(card.len() - 1)
-                                .collect::<String>()
-                                .parse::<u16>()
-                            {
-                                Ok(i) => Ok(i),
-                                Err(err) => {
-                                    match card
-                                        .chars()
-                                        .take(card.len() - 1)
-                                        .collect::<String>()
-                                        .deref()
-                                    {
-                                        "A" => Ok(14_u16),
-                                        "K" => Ok(13_u16),
-                                        "Q" => Ok(12_u16),
-                                        "J" => Ok(11_u16),
-                                        _ => Err(err),
-                                    }
-                                }
-                            }
-                            .ok()
-                            .unwrap(),
-                        });
-                        cards
-                    }),
+                cards: Self::parse_cards(phand),
+                strategy: std::marker::PhantomData,
             };
 
             result.cards.sort_by(|a, b| a.value.cmp(&b.value));
@@ -291,147 +452,107 @@ mod poker_ranking {
             ret
         }
 
+        /// Fallible counterpart of [`from_parse`](Self::from_parse): instead of
+        /// panicking on malformed input, reports why `phand` couldn't be read
+        /// as a five-card hand.
+        pub fn try_from_parse(index: usize, phand: &str) -> Result<Self, PokerError> {
+            if phand.trim().is_empty() {
+                return Err(PokerError::EmptyHand);
+            }
+
+            let cards = phand
+                .split(' ')
+                .map(Card::try_from)
+                .collect::<Result<Vec<Card>, PokerError>>()?;
+
+            if cards.len() != 5 {
+                return Err(PokerError::WrongCardCount(cards.len()));
+            }
+
+            let mut result = Self {
+                id: index,
+                rank: PokerRank::NotRanked,
+                rank_swapped_aces: false,
+                spares: Vec::new(),
+                cards_ranked: Vec::new(),
+                cards,
+                strategy: std::marker::PhantomData,
+            };
+
+            result.cards.sort_by(|a, b| a.value.cmp(&b.value));
+            result.rank_hand();
+            Ok(result)
+        }
+
+        fn parse_cards(phand: &str) -> Vec<Card> {
+            phand
+                .split(" ")
+                .map(Card::parse)
+                .collect()
+        }
+
+        /// Classifies `self.cards` into a [`PokerRank`], populating
+        /// `cards_ranked`/`spares` along the way. A wildcard's suit never
+        /// matches a real suit, so alongside real-suited cards it can only
+        /// break a flush, never complete one — but its literal parsed *value* can still
+        /// complete a straight on its own, without ever routing through
+        /// `S::adjust_counts`. So every candidate category (straight flush,
+        /// flush, straight, and the value-count classification) is computed
+        /// independently and the highest-ranking one wins, rather than
+        /// returning on the first match found.
         fn rank_hand(&mut self) {
-            let mut rank: PokerRank = PokerRank::NotRanked;
-            'rankloop: for ix in (0..=9_u32).into_iter().rev() {
-                match ix {
-                    ix if ix == PokerRank::StraightFlush as u32 => {
-                        if self.aces_swap_over(|cards| {
-                            cards
-                                .into_iter()
-                                .all(|f| f.suit == cards.get(0).unwrap().suit)
-                                && cards.into_iter().enumerate().all(|(idx, card)| {
-                                    idx == 0 || card.value == cards.get(idx - 1).unwrap().value + 1
-                                })
-                        }) {
-                            rank = PokerRank::StraightFlush;
-                            break 'rankloop;
-                        }
-                    }
-                    ix if ix == PokerRank::FourOfAKind as u32
-                        || ix == PokerRank::FullHouse as u32 =>
-                    {
-                        if ix == PokerRank::FourOfAKind as u32 {
-                            let result =
-                                self.cards.iter().fold(HashMap::new(), |mut result, card| {
-                                    result.entry(card.value).or_insert(Vec::new()).push(card);
-                                    result
-                                });
-
-                            if result.len() == 2 {
-                                let mut result = result.values().collect::<Vec<&Vec<&Card>>>();
-                                result.sort_by(|&a, &b| b.len().cmp(&a.len()));
-
-                                match result.get(0).unwrap().len() {
-                                    4 => {
-                                        result.get(0).unwrap().iter().for_each(|&c| {
-                                            self.cards_ranked.push(c.clone());
-                                        });
-                                        result.get(1).unwrap().iter().for_each(|&c| {
-                                            self.spares.push(c.clone());
-                                        });
-                                        rank = PokerRank::FourOfAKind;
-                                        break 'rankloop;
-                                    }
-                                    3 => {
-                                        result.iter().for_each(|v| {
-                                            v.iter().for_each(|&c| {
-                                                self.cards_ranked.push(c.clone());
-                                            })
-                                        });
-                                        rank = PokerRank::FullHouse;
-                                        break 'rankloop;
-                                    }
-                                    _ => (),
-                                }
-                            }
-                        }
-                    }
+            let mut best_rank = PokerRank::NotRanked;
+            let mut best_cards_ranked = Vec::new();
+            let mut best_swapped_aces = false;
+
+            if self.aces_swap_over(|cards| {
+                cards
+                    .into_iter()
+                    .all(|f| f.suit == cards.get(0).unwrap().suit)
+                    && cards.into_iter().enumerate().all(|(idx, card)| {
+                        idx == 0 || card.value == cards.get(idx - 1).unwrap().value + 1
+                    })
+            }) {
+                best_rank = PokerRank::StraightFlush;
+                best_cards_ranked = self.cards_ranked.clone();
+                best_swapped_aces = self.rank_swapped_aces;
+            }
 
-                    ix if ix == PokerRank::Flush as u32 => {
-                        if self
-                            .cards
-                            .iter()
-                            .all(|c| c.suit == self.cards.get(0).unwrap().suit)
-                        {
-                            self.cards
-                                .iter()
-                                .for_each(|c| self.cards_ranked.push(c.clone()));
-                            rank = PokerRank::Flush;
-                            break 'rankloop;
-                        }
-                    }
-                    ix if ix == PokerRank::Straight as u32 => {
-                        if self.aces_swap_over(|cards| {
-                            cards.into_iter().enumerate().all(|(idx, card)| {
-                                idx == 0 || card.value == cards.get(idx - 1).unwrap().value + 1
-                            })
-                        }) {
-                            rank = PokerRank::Straight;
-                            break 'rankloop;
-                        }
-                    }
-                    _ => {
-                        //ix if ix <= PokerRank::ThreeOfAKind as u32 => todo!(), ix if ix == PokerRank::TwoPair as u32 => todo!(), // ix if ix == PokerRank::OnePair as u32 => todo!(), // ix if ix == PokerRank::HighCard as u32 => todo!(),
-                        let result = self.cards.iter().fold(HashMap::new(), |mut result, card| {
-                            result.entry(card.value).or_insert(Vec::new()).push(card);
-                            result
-                        });
-                        let mut result = result.values().collect::<Vec<&Vec<&Card>>>();
-                        result.sort_by(|&a, &b| b.len().cmp(&a.len()));
-                        match result.len() {
-                            3 => {
-                                if result.get(0).unwrap().len() == 3 {
-                                    // PokerRank::ThreeOfAKind
-                                    result.get(0).unwrap().iter().for_each(|&c| {
-                                        self.cards_ranked.push(c.clone());
-                                    });
-                                    result.into_iter().skip(1).for_each(|v| {
-                                        v.iter().for_each(|&c| {
-                                            self.spares.push(c.clone());
-                                        });
-                                    });
-                                    rank = PokerRank::ThreeOfAKind;
-                                    break 'rankloop;
-                                } else {
-                                    // PokerRank::TwoPair
-                                    result.iter().enumerate().for_each(|(idx, &v)| match idx {
-                                        0 | 1 => v.iter().for_each(|&c| {
-                                            self.cards_ranked.push(c.clone());
-                                        }),
-                                        _ => v.iter().for_each(|&c| {
-                                            self.spares.push(c.clone());
-                                        }),
-                                    });
-                                    self.cards_ranked.sort_by(|a, b| b.value.cmp(&a.value));
-                                    rank = PokerRank::TwoPair;
-                                    break 'rankloop;
-                                }
-                            }
-                            4 => {
-                                result.iter().enumerate().for_each(|(idx, &v)| match idx {
-                                    0 => v.iter().for_each(|&c| {
-                                        self.cards_ranked.push(c.clone());
-                                    }),
-                                    _ => v.iter().for_each(|&c| {
-                                        self.spares.push(c.clone());
-                                    }),
-                                });
-                                rank = PokerRank::OnePair;
-                                break 'rankloop;
-                            }
-                            _ => {
-                                result.iter().for_each(|&v| {
-                                    v.iter().for_each(|&c| self.spares.push(c.clone()))
-                                });
-                                rank = PokerRank::HighCard;
-                                break 'rankloop;
-                            }
-                        }
-                    }
-                }
+            if PokerRank::Flush > best_rank
+                && self
+                    .cards
+                    .iter()
+                    .all(|c| c.suit == self.cards.get(0).unwrap().suit)
+            {
+                best_rank = PokerRank::Flush;
+                best_cards_ranked = self.cards.clone();
+                best_swapped_aces = false;
             }
 
+            if PokerRank::Straight > best_rank
+                && self.aces_swap_over(|cards| {
+                    cards.into_iter().enumerate().all(|(idx, card)| {
+                        idx == 0 || card.value == cards.get(idx - 1).unwrap().value + 1
+                    })
+                })
+            {
+                best_rank = PokerRank::Straight;
+                best_cards_ranked = self.cards_ranked.clone();
+                best_swapped_aces = self.rank_swapped_aces;
+            }
+
+            let counts_rank = self.rank_by_counts();
+            if counts_rank > best_rank {
+                best_rank = counts_rank;
+                best_cards_ranked = self.cards_ranked.clone();
+                best_swapped_aces = false;
+            } else {
+                self.spares.clear();
+            }
+
+            self.cards_ranked = best_cards_ranked;
+            self.rank_swapped_aces = best_swapped_aces;
+
             if self.spares.len() > 0 {
                 if self.spares.iter().any(|c| c.value == 14) {
                     self.spares.iter_mut().for_each(|c| {
@@ -443,7 +564,60 @@ mod poker_ranking {
                 self.spares.sort_by(|a, b| b.value.cmp(&a.value));
             }
 
-            self.rank = rank;
+            self.rank = best_rank;
+        }
+
+        fn rank_by_counts(&mut self) -> PokerRank {
+            let mut counts = self.cards.iter().fold(HashMap::new(), |mut map, card| {
+                map.entry(card.value).or_insert(Vec::new()).push(card.clone());
+                map
+            });
+            S::adjust_counts(&mut counts);
+
+            let mut groups = counts.into_values().collect::<Vec<Vec<Card>>>();
+            groups.sort_by(|a, b| b.len().cmp(&a.len()));
+            groups
+                .iter_mut()
+                .for_each(|group| group.sort_by(|a, b| S::card_strength(b).cmp(&S::card_strength(a))));
+
+            match groups.len() {
+                1 => {
+                    self.cards_ranked = groups.remove(0);
+                    PokerRank::FiveOfAKind
+                }
+                2 if groups.get(0).unwrap().len() == 4 => {
+                    self.cards_ranked = groups.remove(0);
+                    self.spares = groups.remove(0);
+                    PokerRank::FourOfAKind
+                }
+                2 => {
+                    self.cards_ranked = groups.remove(0);
+                    self.cards_ranked.extend(groups.remove(0));
+                    PokerRank::FullHouse
+                }
+                3 if groups.get(0).unwrap().len() == 3 => {
+                    self.cards_ranked = groups.remove(0);
+                    self.spares = groups.into_iter().flatten().collect();
+                    PokerRank::ThreeOfAKind
+                }
+                3 => {
+                    self.cards_ranked = groups.remove(0);
+                    self.cards_ranked.extend(groups.remove(0));
+                    self.cards_ranked
+                        .sort_by(|a, b| S::card_strength(b).cmp(&S::card_strength(a)));
+                    self.spares = groups.remove(0);
+                    PokerRank::TwoPair
+                }
+                4 => {
+                    self.cards_ranked = groups.remove(0);
+                    self.spares = groups.into_iter().flatten().collect();
+                    PokerRank::OnePair
+                }
+                _ => {
+                    self.spares = groups.into_iter().flatten().collect();
+                    PokerRank::HighCard
+                }
+            }
         }
 
         fn aces_swap_over(&mut self, cardcheck: impl Fn(&[Card]) -> bool) -> bool {
@@ -475,3 +649,38 @@ mod poker_ranking {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn winning_hands_accepts_unrecognized_suit_characters() {
+        // Regression test: Card::parse must stay lenient on suit characters
+        // it doesn't recognize, treating them as a joker rather than
+        // panicking, since winning_hands has never errored on this input.
+        let hands = ["4H 4H 4H 4H 4X", "2H 3D 4C 5S AS"];
+        assert_eq!(winning_hands(&hands), vec!["4H 4H 4H 4H 4X"]);
+    }
+
+    #[test]
+    fn winning_hands_wild_promotes_joker_to_five_of_a_kind() {
+        let hands = ["4H 4S 4D 4C 9*", "AH KH QH JH TH"];
+        assert_eq!(winning_hands_wild(&hands), vec!["4H 4S 4D 4C 9*"]);
+    }
+
+    #[test]
+    fn try_winning_hands_reports_invalid_suit_instead_of_panicking() {
+        let hands = ["4H 4H 4H 4H 4X", "2H 3D 4C 5S AS"];
+        assert_eq!(
+            try_winning_hands(&hands),
+            Err(PokerError::InvalidSuit('X'))
+        );
+    }
+
+    #[test]
+    fn single_character_ten_notation_matches_two_character_notation() {
+        let hands = ["TH 9H 8H 7H 6H", "10H 9H 8H 7H 6H"];
+        assert_eq!(winning_hands(&hands), vec!["TH 9H 8H 7H 6H", "10H 9H 8H 7H 6H"]);
+    }
+}